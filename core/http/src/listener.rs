@@ -0,0 +1,72 @@
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+
+use rustls::Certificate;
+
+use crate::tls::listener::HandshakeInfo;
+
+/// A listener: something that accepts incoming connections, each of which
+/// implements [`Connection`].
+pub trait Listener {
+    /// The connection type returned by `poll_accept()`.
+    type Connection: Connection;
+
+    /// The local address this listener is bound to, if known.
+    fn local_addr(&self) -> Option<SocketAddr>;
+
+    /// Try to accept a new incoming connection, registering the current
+    /// task to be woken when one becomes available.
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<Self::Connection>>;
+}
+
+/// A single accepted connection, as returned by a [`Listener`].
+pub trait Connection: Send + Unpin {
+    /// The remote address of this connection, if known.
+    fn peer_address(&self) -> Option<SocketAddr>;
+
+    /// Enable `TCP_NODELAY` on the underlying socket, if applicable.
+    fn enable_nodelay(&self) -> io::Result<()>;
+
+    /// The peer's certificate chain, for connections that support peer
+    /// certificates. The returned `Certificates` is empty (its `chain()` is
+    /// `None`) when the peer presented no chain or it isn't known yet.
+    /// Defaults to `None` for connections that don't support peer
+    /// certificates (e.g. plaintext TCP).
+    fn peer_certificates(&self) -> Option<Certificates> {
+        None
+    }
+
+    /// TLS handshake metadata for this connection, if any. Defaults to
+    /// `None` so non-TLS `Connection` impls don't need to know about it.
+    fn handshake_info(&self) -> Option<HandshakeInfo> {
+        None
+    }
+}
+
+/// A peer's certificate chain, populated once it's known.
+///
+/// A `Connection` may hand out its `Certificates` before the peer's
+/// certificate chain is known, then populate it as soon as it is (a
+/// `TlsStream` still in its handshaking state does this; `TlsListener` itself
+/// only yields streams whose handshake has completed). `Certificates` starts
+/// empty and is populated at most once.
+#[derive(Clone, Default)]
+pub struct Certificates(Arc<OnceLock<Vec<Certificate>>>);
+
+impl Certificates {
+    /// Populate the certificate chain. A no-op if already set.
+    pub fn set(&self, chain: Vec<Certificate>) {
+        let _ = self.0.set(chain);
+    }
+
+    /// The certificate chain, if it's been set.
+    pub fn chain(&self) -> Option<&[Certificate]> {
+        self.0.get().map(Vec::as_slice)
+    }
+}
@@ -1,69 +1,134 @@
+use std::collections::HashMap;
 use std::future::Future;
 use std::io;
 use std::net::SocketAddr;
 use std::pin::Pin;
 
-use std::sync::{Arc, Mutex, RwLock};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 use std::task::{Context, Poll};
+use std::time::Duration;
+#[cfg(unix)]
 use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::{mpsc, oneshot};
 
+use futures::stream::{FuturesUnordered, StreamExt};
 use rustls::server::ClientHello;
 use rustls::{sign::CertifiedKey, PrivateKey};
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::{TcpListener, TcpStream};
+use tokio::time::error::Elapsed;
 use tokio_rustls::{server::TlsStream as BareTlsStream, Accept, TlsAcceptor};
 
 use crate::listener::{Certificates, Connection, Listener};
 use crate::tls::util::{load_ca_certs, load_certs, load_private_key};
 use rustls::Certificate;
 
+/// The result of racing a single handshake against its timeout, tagged with
+/// the peer address so we can log which client it belonged to.
+type HandshakeResult = (SocketAddr, Result<io::Result<BareTlsStream<TcpStream>>, Elapsed>);
+
+/// A handshake in flight: a boxed future so `TlsListener` can hold an
+/// arbitrary number of them in a single `FuturesUnordered`.
+type PendingHandshake = Pin<Box<dyn Future<Output = HandshakeResult> + Send>>;
+
+/// A re-openable source of cert/key PEM data: called once for the initial
+/// load and again on every reload, so a reload actually re-reads from disk
+/// (or wherever `R` comes from) instead of replaying an exhausted reader.
+pub type CertSource<R> = Arc<dyn Fn() -> io::Result<R> + Send + Sync>;
+
 pub struct ResolverConfig {
-    cert_chain: Vec<Certificate>,
-    private_key: PrivateKey,
+    /// Used when the client sends no SNI name, or one that matches nothing
+    /// in `sni_keys`.
+    default_key: Arc<CertifiedKey>,
+    /// Keyed by lowercase ASCII hostname, e.g. `"example.com"` or the
+    /// single-label wildcard `"*.example.com"`.
+    sni_keys: HashMap<String, Arc<CertifiedKey>>,
 }
 
 pub struct Resolver {
-    config: Arc<Mutex<ResolverConfig>>,
+    config: Arc<RwLock<ResolverConfig>>,
+}
+
+/// A handle to a running `Resolver::background_updater` task.
+///
+/// Dropping the handle ends the channel-driven reload task, so no further
+/// reloads can be requested through it; on Unix, the separate SIGUSR1 task
+/// keeps working regardless.
+///
+/// Named `ReloadHandle` rather than `ReloadTrigger`: it's a channel sender
+/// paired with an async `reload()` that awaits the result, not a one-shot
+/// fire-and-forget trigger, and "handle" matches how the rest of this file
+/// names similar owned references (e.g. `TlsListener::reload_handle()`).
+pub struct ReloadHandle {
+    tx: mpsc::Sender<oneshot::Sender<io::Result<()>>>,
+}
+
+impl ReloadHandle {
+    /// Ask the background updater to reload certs now, and await whether
+    /// the reload succeeded. On failure, the updater keeps the certs it had.
+    pub async fn reload(&self) -> io::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.tx.send(tx).await.map_err(|_| {
+            io::Error::new(io::ErrorKind::Other, "TLS cert background updater is gone")
+        })?;
+
+        rx.await.map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "TLS cert background updater dropped the reload request",
+            )
+        })?
+    }
 }
 
 /// A TLS listener over TCP.
+///
+/// Handshakes are not driven inline in `poll_accept()`. Instead, each
+/// accepted TCP connection is handed to `acceptor.accept()`, the resulting
+/// future is raced against `handshake_timeout`, and the pair is pushed into
+/// `handshakes`, a `FuturesUnordered` of in-flight handshakes bounded by
+/// `max_concurrent_handshakes`. `poll_accept()` tops up `handshakes` from the
+/// TCP listener up to that bound, then polls `handshakes` for the next one to
+/// finish. A handshake that errors or times out is logged and dropped; it is
+/// never handed to Rocket. This keeps a slow or malicious client that opens a
+/// TCP connection but stalls the handshake from occupying a worker, and
+/// bounds memory/fd usage under a handshake flood.
 pub struct TlsListener {
     listener: TcpListener,
     acceptor: TlsAcceptor,
+    handshake_timeout: Duration,
+    max_concurrent_handshakes: usize,
+    handshakes: FuturesUnordered<PendingHandshake>,
+    reload_handle: ReloadHandle,
 }
 
-/// This implementation exists so that ROCKET_WORKERS=1 can make progress while
-/// a TLS handshake is being completed. It does this by returning `Ready` from
-/// `poll_accept()` as soon as we have a TCP connection and performing the
-/// handshake in the `AsyncRead` and `AsyncWrite` implementations.
-///
-/// A straight-forward implementation of this strategy results in none of the
-/// TLS information being available at the time the connection is "established",
-/// that is, when `poll_accept()` returns, since the handshake has yet to occur.
-/// Importantly, certificate information isn't available at the time that we
-/// request it.
-///
-/// The underlying problem is e hyper's "Accept" trait. Were we to manage
-/// connections ourselves, we'd likely want to:
-///
-///   1. Stop blocking the worker as soon as we have a TCP connection.
-///   2. Perform the handshake in the background.
-///   3. Give the connection to Rocket when/if the handshake is done.
-///
-/// See hyperium/hyper/issues/2321 for more details.
-///
-/// To work around this, we "lie" when `peer_certificates()` are requested and
-/// always return `Some(Certificates)`. Internally, `Certificates` is an
-/// `Arc<InitCell<Vec<CertificateData>>>`, effectively a shared, thread-safe,
-/// `OnceCell`. The cell is initially empty and is filled as soon as the
-/// handshake is complete. If the certificate data were to be requested prior to
-/// this point, it would be empty. However, in Rocket, we only request
-/// certificate data when we have a `Request` object, which implies we're receiving payload data, which implies the TLS handshake has finished, so the
-/// certificate data as seen by a Rocket application will always be "fresh".
+/// Because handshakes now complete before a `TlsStream` is ever handed to
+/// Rocket (see `TlsListener`), `peer_certificates()` is no longer a "lie":
+/// `certs` is fully populated by the time `poll_accept()` returns. The
+/// `Handshaking` state below is retained because the `AsyncRead`/`AsyncWrite`
+/// impls still need somewhere to poll a handshake to completion if one is
+/// ever constructed in that state.
 pub struct TlsStream {
     remote: SocketAddr,
     state: TlsState,
     certs: Certificates,
+    handshake_info: Arc<OnceLock<HandshakeInfo>>,
+}
+
+/// TLS handshake metadata exposed to Rocket applications via
+/// `Connection::handshake_info()`. Populated once, from the
+/// `ServerConnection`, as soon as the handshake completes; see
+/// `capture_handshake_info`.
+#[derive(Debug, Clone, Default)]
+pub struct HandshakeInfo {
+    /// The negotiated ALPN protocol (e.g. `b"h2"` or `b"http/1.1"`), if any.
+    pub alpn_protocol: Option<Vec<u8>>,
+    /// The negotiated TLS protocol version.
+    pub version: Option<rustls::ProtocolVersion>,
+    /// The negotiated cipher suite.
+    pub cipher_suite: Option<rustls::SupportedCipherSuite>,
+    /// The SNI hostname the client requested, if any.
+    pub sni_hostname: Option<String>,
 }
 
 /// State of `TlsStream`.
@@ -79,85 +144,247 @@ pub struct Config<R>
 where
     R: io::BufRead + std::marker::Send + std::marker::Sync + 'static,
 {
-    pub cert_chain: R,
-    pub private_key: R,
+    /// Re-opened on every reload, so hot-reload actually picks up changes
+    /// from disk rather than replaying the bytes read at startup.
+    pub cert_chain: CertSource<R>,
+    pub private_key: CertSource<R>,
     pub ciphersuites: Vec<rustls::SupportedCipherSuite>,
     pub prefer_server_order: bool,
     pub ca_certs: Option<R>,
     pub mandatory_mtls: bool,
+    /// Additional `(hostname, cert_chain, private_key)` triples used to
+    /// serve multiple virtual hosts from a single `TlsListener`, selected by
+    /// SNI. `cert_chain`/`private_key` above remain the default, used when
+    /// the client sends no SNI name or one that matches none of these.
+    pub sni_certs: Vec<(String, CertSource<R>, CertSource<R>)>,
+    /// Maximum time a single TLS handshake may take before it's abandoned.
+    pub handshake_timeout: Duration,
+    /// Maximum number of TLS handshakes `TlsListener` will drive at once.
+    pub max_concurrent_handshakes: usize,
+    /// Maximum amount of 0-RTT early data rustls will accept at the TLS
+    /// layer, in bytes. `0` (the default) disables it. This only controls
+    /// what rustls is willing to buffer during the handshake; nothing in
+    /// this module reads that data back out yet (see `capture_handshake_info`),
+    /// so setting this above `0` has no user-visible effect beyond rustls
+    /// accepting the early data flight instead of rejecting it.
+    pub max_early_data_size: u32,
+}
+
+impl<R> Config<R>
+where
+    R: io::BufRead + std::marker::Send + std::marker::Sync + 'static,
+{
+    /// Build a `Config`, defaulting the fields this crate has added since
+    /// `TlsConfig` last constructed one directly: no extra SNI certs, a 10s
+    /// handshake timeout, a 1024-handshake concurrency bound, and early data
+    /// off. Callers supply only the fields below, which predate the four new
+    /// ones, though `cert_chain` and `private_key` are now `CertSource<R>`
+    /// closures rather than readers; `rocket` core's `TlsConfig` -> `Config`
+    /// conversion should route through this (or set the four new fields
+    /// explicitly) rather than building the struct literal directly, since
+    /// that struct literal no longer compiles with only the old fields.
+    pub fn new(
+        cert_chain: CertSource<R>,
+        private_key: CertSource<R>,
+        ciphersuites: Vec<rustls::SupportedCipherSuite>,
+        prefer_server_order: bool,
+        ca_certs: Option<R>,
+        mandatory_mtls: bool,
+    ) -> Self {
+        Self {
+            cert_chain,
+            private_key,
+            ciphersuites,
+            prefer_server_order,
+            ca_certs,
+            mandatory_mtls,
+            sni_certs: Vec::new(),
+            handshake_timeout: Duration::from_secs(10),
+            max_concurrent_handshakes: 1024,
+            max_early_data_size: 0,
+        }
+    }
 }
 
 impl rustls::server::ResolvesServerCert for Resolver {
-    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
-        let config = self.config.lock().unwrap();
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        let config = self.config.read().unwrap();
+
+        let key = match client_hello.server_name() {
+            Some(name) => find_sni_key(&name.to_ascii_lowercase(), &config.sni_keys),
+            None => None,
+        };
+
+        Some(key.unwrap_or(&config.default_key).clone())
+    }
+}
+
+/// Find the cert for `name` (already lowercased) in `sni_keys`, trying an
+/// exact match first, then a single-label wildcard: `"foo.example.com"`
+/// matches `"*.example.com"`, but `"foo.bar.example.com"` does not.
+///
+/// Generic over the map's value type so the matching logic can be unit
+/// tested without constructing real `CertifiedKey`s.
+fn find_sni_key<'a, V>(name: &str, sni_keys: &'a HashMap<String, V>) -> Option<&'a V> {
+    if let Some(key) = sni_keys.get(name) {
+        return Some(key);
+    }
+
+    let (_, parent) = name.split_once('.')?;
+    sni_keys.get(&format!("*.{}", parent))
+}
+
+#[cfg(test)]
+mod sni_resolution_tests {
+    use super::*;
+
+    #[test]
+    fn exact_match_wins_over_wildcard() {
+        let mut sni_keys = HashMap::new();
+        sni_keys.insert("foo.example.com".to_string(), "exact");
+        sni_keys.insert("*.example.com".to_string(), "wildcard");
+
+        assert_eq!(find_sni_key("foo.example.com", &sni_keys), Some(&"exact"));
+    }
+
+    #[test]
+    fn single_label_wildcard_matches() {
+        let mut sni_keys = HashMap::new();
+        sni_keys.insert("*.example.com".to_string(), "wildcard");
+
+        assert_eq!(find_sni_key("foo.example.com", &sni_keys), Some(&"wildcard"));
+    }
+
+    #[test]
+    fn wildcard_does_not_match_two_labels_deep() {
+        let mut sni_keys = HashMap::new();
+        sni_keys.insert("*.example.com".to_string(), "wildcard");
+
+        assert_eq!(find_sni_key("foo.bar.example.com", &sni_keys), None);
+    }
+
+    #[test]
+    fn no_match_returns_none() {
+        let sni_keys: HashMap<String, &str> = HashMap::new();
+        assert_eq!(find_sni_key("foo.example.com", &sni_keys), None);
+    }
+
+    #[test]
+    fn uppercase_sni_is_folded_before_lookup() {
+        // Mirrors `Resolver::resolve`'s `to_ascii_lowercase()` call:
+        // `find_sni_key` folds neither `name` nor the map's keys, so callers
+        // lowercase both (`build_resolver_config` lowercases the keys).
+        let mut sni_keys = HashMap::new();
+        sni_keys.insert("foo.example.com".to_string(), "exact");
 
-        let cert_chain = &config.cert_chain;
-        let private_key = &config.private_key;
+        let folded = "FOO.EXAMPLE.COM".to_ascii_lowercase();
+        assert_eq!(find_sni_key(&folded, &sni_keys), Some(&"exact"));
+    }
+}
 
-        let sign_key = rustls::sign::any_supported_type(private_key).unwrap();
+/// Load a cert chain and private key pair into a ready-to-serve `CertifiedKey`.
+fn load_certified_key<R: io::BufRead>(
+    mut cert_chain: R,
+    mut private_key: R,
+) -> io::Result<Arc<CertifiedKey>> {
+    let cert_chain: Vec<Certificate> = load_certs(&mut cert_chain)
+        .map_err(|e| io::Error::new(e.kind(), format!("bad TLS cert chain: {}", e)))?;
 
-        let cert = Arc::new(CertifiedKey::new(cert_chain.to_vec(), sign_key));
+    let private_key: PrivateKey = load_private_key(&mut private_key)
+        .map_err(|e| io::Error::new(e.kind(), format!("bad TLS private key: {}", e)))?;
 
-        Some(cert)
+    let sign_key = rustls::sign::any_supported_type(&private_key)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("bad TLS private key: {}", e)))?;
+
+    Ok(Arc::new(CertifiedKey::new(cert_chain, sign_key)))
+}
+
+/// Parse the default and all SNI cert/key pairs out of `config` into a
+/// fresh `ResolverConfig`, re-opening each `CertSource` so every call — the
+/// initial load and every later reload — reads fresh bytes rather than
+/// replaying an already-exhausted reader.
+fn build_resolver_config<R: io::BufRead>(config: &Config<R>) -> io::Result<ResolverConfig> {
+    let default_key = load_certified_key((config.cert_chain)()?, (config.private_key)()?)?;
+
+    let mut sni_keys = HashMap::new();
+    for (hostname, cert_chain, private_key) in &config.sni_certs {
+        let key = load_certified_key(cert_chain()?, private_key()?)?;
+        sni_keys.insert(hostname.to_ascii_lowercase(), key);
     }
+
+    Ok(ResolverConfig {
+        default_key,
+        sni_keys,
+    })
+}
+
+/// Reload `c` from disk and, only if parsing succeeds, swap the result into
+/// `target`. On failure, `target` is left untouched and the error is
+/// returned so the caller can log or report it; no one panics on a bad
+/// reload.
+fn reload_certs<R>(c: &Mutex<Config<R>>, target: &RwLock<ResolverConfig>) -> io::Result<()>
+where
+    R: io::BufRead,
+{
+    let config = c.lock().unwrap();
+    let resolver_config = build_resolver_config(&config)?;
+    drop(config);
+    *target.write().unwrap() = resolver_config;
+    Ok(())
 }
 
 impl Resolver {
-    pub fn new<R>(c: Arc<Mutex<Config<R>>>) -> Self
+    pub fn new<R>(c: Arc<Mutex<Config<R>>>) -> io::Result<Self>
     where
         R: io::BufRead + std::marker::Send + std::marker::Sync + 'static,
     {
-        let mut config = c.lock().unwrap();
+        let config = c.lock().unwrap();
+        let resolver_config = build_resolver_config(&config)?;
+        drop(config);
 
-        let cert_chain: Vec<Certificate> = load_certs(&mut config.cert_chain)
-            .map_err(|e| io::Error::new(e.kind(), format!("bad TLS cert chain: {}", e)))
-            .unwrap();
-
-        let private_key: PrivateKey = load_private_key(&mut config.private_key)
-            .map_err(|e| io::Error::new(e.kind(), format!("bad TLS private key: {}", e)))
-            .unwrap();
-
-        Self {
-            config: Arc::new(Mutex::new(ResolverConfig {
-                cert_chain,
-                private_key,
-            })),
-        }
+        Ok(Self {
+            config: Arc::new(RwLock::new(resolver_config)),
+        })
     }
 
+    /// Spawn a task that reloads TLS certs whenever asked, returning a
+    /// [`ReloadHandle`] the caller can use to trigger a reload and await
+    /// whether it succeeded. On Unix, a second task also reloads on
+    /// `SIGUSR1`, for operators used to the old behavior; that path has no
+    /// one to report success or failure to, so it just logs.
     pub fn background_updater<R>(
         &mut self,
         c: Arc<Mutex<Config<R>>>,
-    ) -> Result<bool, Box<dyn std::error::Error>>
+    ) -> Result<ReloadHandle, Box<dyn std::error::Error>>
     where
         R: io::BufRead + std::marker::Send + std::marker::Sync + 'static,
     {
-        let mut _stream = signal(SignalKind::user_defined1())?;
-
-        let local_self = Arc::clone(&self.config);
+        let (tx, mut rx) = mpsc::channel::<oneshot::Sender<io::Result<()>>>(1);
 
+        let channel_config = Arc::clone(&c);
+        let channel_target = Arc::clone(&self.config);
         tokio::spawn(async move {
-            loop {
-                _stream.recv().await;
-
-                let mut config = c.lock().unwrap();
-
-                let cert_chain = load_certs(&mut config.cert_chain)
-                    .map_err(|e| io::Error::new(e.kind(), format!("bad TLS cert chain: {}", e)))
-                    .unwrap();
-
-                let private_key = load_private_key(&mut config.private_key)
-                    .map_err(|e| io::Error::new(e.kind(), format!("bad TLS private key: {}", e)))
-                    .unwrap();
-
-                *local_self.lock().unwrap() = ResolverConfig {
-                    cert_chain,
-                    private_key,
-                };
+            while let Some(done) = rx.recv().await {
+                let _ = done.send(reload_certs(&channel_config, &channel_target));
             }
         });
 
-        Ok(true)
+        #[cfg(unix)]
+        {
+            let mut signal = signal(SignalKind::user_defined1())?;
+            let signal_config = Arc::clone(&c);
+            let signal_target = Arc::clone(&self.config);
+            tokio::spawn(async move {
+                loop {
+                    signal.recv().await;
+                    if let Err(e) = reload_certs(&signal_config, &signal_target) {
+                        log::error!("SIGUSR1 TLS cert reload failed, keeping old certs: {}", e);
+                    }
+                }
+            });
+        }
+
+        Ok(ReloadHandle { tx })
     }
 }
 
@@ -181,12 +408,22 @@ impl TlsListener {
         let cipher_suite = &c.ciphersuites.to_vec();
 
         let prefer_server_order = c.prefer_server_order;
+        let handshake_timeout = c.handshake_timeout;
+        let max_concurrent_handshakes = c.max_concurrent_handshakes;
+        let max_early_data_size = c.max_early_data_size;
+
+        if max_concurrent_handshakes == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "max_concurrent_handshakes must be at least 1",
+            ));
+        }
 
         let arc_config = Arc::new(Mutex::new(c));
         let background_config = Arc::clone(&arc_config);
-        let mut resolver = Resolver::new(arc_config);
+        let mut resolver = Resolver::new(arc_config)?;
 
-        resolver
+        let reload_handle = resolver
             .background_updater(background_config)
             .map_err(|e| {
                 io::Error::new(
@@ -215,9 +452,72 @@ impl TlsListener {
             io::Error::new(io::ErrorKind::Other, format!("bad TLS ticketer: {}", e))
         })?;
 
+        if max_early_data_size != 0 {
+            tls_config.max_early_data_size = max_early_data_size;
+        }
+
         let listener = TcpListener::bind(addr).await?;
         let acceptor = TlsAcceptor::from(Arc::new(tls_config));
-        Ok(TlsListener { listener, acceptor })
+        Ok(TlsListener {
+            listener,
+            acceptor,
+            handshake_timeout,
+            max_concurrent_handshakes,
+            handshakes: FuturesUnordered::new(),
+            reload_handle,
+        })
+    }
+
+    /// A handle to trigger a TLS cert reload and await whether it succeeded.
+    /// See [`ReloadHandle::reload`].
+    pub fn reload_handle(&self) -> &ReloadHandle {
+        &self.reload_handle
+    }
+}
+
+/// Capture the peer's certificate chain and handshake metadata out of a
+/// just-completed `ServerConnection` into `certs`/`info`.
+///
+/// This deliberately does NOT read back any 0-RTT early data the client may
+/// have sent, even though `Config::max_early_data_size` lets rustls accept
+/// it at the TLS layer. Doing so needs `ServerConnection::early_data()`
+/// (server-side absent on some rustls 0.21.x revisions) and a live handshake
+/// to confirm it still yields buffered plaintext after `TlsAcceptor::accept()`
+/// resolves — neither is verified in this checkout, and early data is
+/// replayable, so it isn't safe to ship on an unverified assumption. Land
+/// that as a follow-up once an end-to-end 0-RTT test exists in rocket
+/// core's TLS integration suite.
+fn capture_handshake_info(
+    stream: &mut BareTlsStream<TcpStream>,
+    certs: &Certificates,
+    info: &OnceLock<HandshakeInfo>,
+) {
+    let conn = stream.get_mut().1;
+
+    if let Some(cert_chain) = conn.peer_certificates() {
+        certs.set(cert_chain.to_vec());
+    }
+
+    let _ = info.set(HandshakeInfo {
+        alpn_protocol: conn.alpn_protocol().map(|p| p.to_vec()),
+        version: conn.protocol_version(),
+        cipher_suite: conn.negotiated_cipher_suite(),
+        sni_hostname: conn.server_name().map(str::to_string),
+    });
+}
+
+/// Finish building a `TlsStream` for a handshake that's already completed,
+/// capturing the peer's certificate chain and handshake metadata up front.
+fn finish_handshake(remote: SocketAddr, mut stream: BareTlsStream<TcpStream>) -> TlsStream {
+    let certs = Certificates::default();
+    let handshake_info = Arc::new(OnceLock::new());
+    capture_handshake_info(&mut stream, &certs, &handshake_info);
+
+    TlsStream {
+        remote,
+        state: TlsState::Streaming(stream),
+        certs,
+        handshake_info,
     }
 }
 
@@ -232,14 +532,44 @@ impl Listener for TlsListener {
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<io::Result<Self::Connection>> {
-        match futures::ready!(self.listener.poll_accept(cx)) {
-            Ok((io, addr)) => Poll::Ready(Ok(TlsStream {
-                remote: addr,
-                state: TlsState::Handshaking(self.acceptor.accept(io)),
-                // These are empty and filled in after handshake is complete.
-                certs: Certificates::default(),
-            })),
-            Err(e) => Poll::Ready(Err(e)),
+        let this = self.get_mut();
+
+        loop {
+            // Top up in-flight handshakes from the TCP listener, up to our
+            // concurrency bound, so one slow handshake can't starve others.
+            while this.handshakes.len() < this.max_concurrent_handshakes {
+                match this.listener.poll_accept(cx) {
+                    Poll::Ready(Ok((io, addr))) => {
+                        let accept = this.acceptor.accept(io);
+                        let timeout = this.handshake_timeout;
+                        this.handshakes
+                            .push(Box::pin(async move { (addr, tokio::time::timeout(timeout, accept).await) }));
+                    }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => break,
+                }
+            }
+
+            match this.handshakes.poll_next_unpin(cx) {
+                Poll::Ready(Some((remote, Ok(Ok(stream))))) => {
+                    return Poll::Ready(Ok(finish_handshake(remote, stream)));
+                }
+                Poll::Ready(Some((remote, Ok(Err(e))))) => {
+                    log::warn!("tls handshake with {} failed: {}", remote, e);
+                }
+                Poll::Ready(Some((remote, Err(_)))) => {
+                    log::warn!(
+                        "tls handshake with {} timed out after {:?}",
+                        remote,
+                        this.handshake_timeout
+                    );
+                }
+                // No in-flight handshakes to report on right now. We just
+                // polled the listener above, so its waker is registered if
+                // it had nothing for us either; it's safe to sleep.
+                Poll::Ready(None) => return Poll::Pending,
+                Poll::Pending => return Poll::Pending,
+            }
         }
     }
 }
@@ -266,6 +596,10 @@ impl Connection for TlsStream {
     fn peer_certificates(&self) -> Option<Certificates> {
         Some(self.certs.clone())
     }
+
+    fn handshake_info(&self) -> Option<HandshakeInfo> {
+        self.handshake_info.get().cloned()
+    }
 }
 
 impl TlsStream {
@@ -281,11 +615,8 @@ impl TlsStream {
             match self.state {
                 TlsState::Handshaking(ref mut accept) => {
                     match futures::ready!(Pin::new(accept).poll(cx)) {
-                        Ok(stream) => {
-                            if let Some(cert_chain) = stream.get_ref().1.peer_certificates() {
-                                self.certs.set(cert_chain.to_vec());
-                            }
-
+                        Ok(mut stream) => {
+                            capture_handshake_info(&mut stream, &self.certs, &self.handshake_info);
                             self.state = TlsState::Streaming(stream);
                         }
                         Err(e) => {
@@ -302,7 +633,7 @@ impl TlsStream {
 
 impl AsyncRead for TlsStream {
     fn poll_read(
-        self: Pin<&mut Self>,
+        mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &mut tokio::io::ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {